@@ -3,6 +3,9 @@
 //     https://opensource.org/licenses/Apache-2.0
 
 use super::ImplementationError;
+use indexmap::IndexMap;
+use sfv::SerializeValue;
+use std::fmt;
 
 /// [Signature component parameters](https://www.rfc-editor.org/rfc/rfc9421#name-http-signature-component-pa) for HTTP fields.
 #[derive(Clone, Debug, PartialEq, Hash, Eq)]
@@ -231,6 +234,26 @@ impl TryFrom<HTTPField> for sfv::Item {
     }
 }
 
+// Render a component identifier as it appears in `Signature-Input` and signature bases, falling
+// back to `fallback` in quotes if it can't be serialized, e.g. for an invalid HTTP field name.
+fn write_identifier(
+    f: &mut fmt::Formatter<'_>,
+    item: Result<sfv::Item, ImplementationError>,
+    fallback: &str,
+) -> fmt::Result {
+    match item {
+        Ok(item) => f.write_str(&item.serialize_value()),
+        Err(_) => write!(f, "{fallback:?}"),
+    }
+}
+
+impl fmt::Display for HTTPField {
+    /// Renders the component identifier, e.g. `"example-dict";key="a"`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_identifier(f, sfv::Item::try_from(self.clone()), &self.name)
+    }
+}
+
 /// [Signature component parameters](https://www.rfc-editor.org/rfc/rfc9421#name-http-signature-component-pa)
 /// specifically for the `@query-params` derived component.
 #[derive(Clone, Debug, PartialEq, Hash, Eq)]
@@ -261,7 +284,9 @@ pub enum DerivedComponent {
         /// Indicates this HTTP header value was obtained from the request. Typically only used in a signed response
         req: bool,
     },
-    /// Represents `@method` derived component
+    /// Represents `@method` derived component. Its value is case-sensitive and is not
+    /// normalized when generating signature bases, so both sides must agree on it, see
+    /// `method_value`.
     Method {
         /// Indicates this HTTP header value was obtained from the request. Typically only used in a signed response
         req: bool,
@@ -271,12 +296,14 @@ pub enum DerivedComponent {
         /// Indicates this HTTP header value was obtained from the request. Typically only used in a signed response
         req: bool,
     },
-    /// Represents `@scheme` derived component
+    /// Represents `@scheme` derived component. Its value must be lowercase; signature bases
+    /// are generated with the value lowercased regardless, see `scheme_value`.
     Scheme {
         /// Indicates this HTTP header value was obtained from the request. Typically only used in a signed response
         req: bool,
     },
-    /// Represents `@query` derived component
+    /// Represents `@query` derived component. Its value always begins with `?`, even when the
+    /// query is empty or absent, see `query_value`.
     Query {
         /// Indicates this HTTP header value was obtained from the request. Typically only used in a signed response
         req: bool,
@@ -294,7 +321,9 @@ pub enum DerivedComponent {
 }
 
 /// A container that represents an ordered list of signature component fields. Order is significant during signing and
-/// verifying.
+/// verifying. Parameters are kept in the order they were parsed in, and serialized in the order they are listed, so
+/// `"@query-param";name="a";req` and `"@query-param";req;name="a"` each round-trip unchanged. As the verifier
+/// re-serializes the identifier exactly as the signer wrote it in `Signature-Input`, both always agree on it.
 #[derive(Clone, Debug, PartialEq, Hash, Eq)]
 pub struct QueryParamParametersSet(pub Vec<QueryParamParameters>);
 
@@ -348,7 +377,7 @@ impl TryFrom<sfv::Parameters> for QueryParamParametersSet {
                 }
             }
         }
-        Ok(QueryParamParametersSet(output.into_iter().collect()))
+        Ok(QueryParamParametersSet(output))
     }
 }
 
@@ -420,32 +449,191 @@ impl TryFrom<DerivedComponent> for sfv::Item {
             DerivedComponent::Scheme { req } => template("@scheme", req),
             DerivedComponent::Status { req } => template("@status", req),
             DerivedComponent::Query { req } => template("@query", req),
-            DerivedComponent::QueryParams { parameters } => {
-                let mut sfv_parameters = sfv::Parameters::new();
-                for param in &parameters.0 {
-                    match param {
-                        QueryParamParameters::Req => {
-                            let key = sfv::KeyRef::constant("req").to_owned();
-                            sfv_parameters.insert(key, sfv::BareItem::Boolean(true));
-                        }
-                        QueryParamParameters::Name(name) => {
-                            let key = sfv::KeyRef::constant("name").to_owned();
-                            let value = sfv::String::from_string(name.clone())
-                                .map_err(|(e, _)| ImplementationError::ImpossibleSfvError(e))?;
-                            sfv_parameters.insert(key, sfv::BareItem::String(value));
-                        }
-                    }
-                }
+            DerivedComponent::QueryParams { parameters } => Ok(sfv::Item {
+                bare_item: sfv::BareItem::String(
+                    sfv::String::from_string("@query-param".to_string())
+                        .map_err(|(e, _)| ImplementationError::ImpossibleSfvError(e))?,
+                ),
+                params: parameters.try_into()?,
+            }),
+        }
+    }
+}
 
-                Ok(sfv::Item {
-                    bare_item: sfv::BareItem::String(
-                        sfv::String::from_string("@query-param".to_string())
-                            .map_err(|(e, _)| ImplementationError::ImpossibleSfvError(e))?,
-                    ),
-                    params: parameters.try_into()?,
-                })
+impl fmt::Display for DerivedComponent {
+    /// Renders the component identifier, e.g. `"@authority";req`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_identifier(f, sfv::Item::try_from(self.clone()), "@unknown")
+    }
+}
+
+/// Compute the value of the HTTP field `name` from `fields`, a message's `(name, value)` header
+/// pairs, per [RFC 9421](https://www.rfc-editor.org/rfc/rfc9421#name-http-fields). Field names are
+/// matched case-insensitively, and the values of every instance combined as in
+/// `combine_field_values`. Returns `None` if the field is absent. Intended as a building block for
+/// `SignedMessage::lookup_component` and `UnsignedMessage::fetch_components_to_cover`
+/// implementations backed by headers whose names are not lowercased.
+pub fn http_field_value<'a>(
+    fields: impl IntoIterator<Item = (&'a str, &'a str)>,
+    name: &str,
+) -> Option<String> {
+    let values: Vec<&str> = fields
+        .into_iter()
+        .filter(|(field_name, _)| field_name.trim().eq_ignore_ascii_case(name))
+        .map(|(_, value)| value)
+        .collect();
+
+    if values.is_empty() {
+        None
+    } else {
+        Some(combine_field_values(&values))
+    }
+}
+
+/// Combine the `values` of every instance of an HTTP field, in the order they appear in the
+/// message, into the single value used in the signature base, per
+/// [RFC 9421](https://www.rfc-editor.org/rfc/rfc9421#name-http-fields): leading and trailing
+/// whitespace is trimmed from each value, obsolete line folding is replaced by a single space,
+/// and the results are joined with `, `. Does not apply to fields covered with the `bs`
+/// parameter, whose instances are each wrapped individually instead.
+pub fn combine_field_values(values: &[&str]) -> String {
+    values
+        .iter()
+        .map(|value| {
+            value
+                .split(['\r', '\n'])
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Compute the value of the [`@status`](https://www.rfc-editor.org/rfc/rfc9421#name-status) derived
+/// component from a response status code: its three-digit representation, without quotes.
+///
+/// # Errors
+///
+/// Returns `ImplementationError::ParsingError` if `status` is not a three-digit status code.
+pub fn status_value(status: u16) -> Result<String, ImplementationError> {
+    if (100..=999).contains(&status) {
+        Ok(status.to_string())
+    } else {
+        Err(ImplementationError::ParsingError(format!(
+            "`@status` must be a three-digit status code, got `{status}`"
+        )))
+    }
+}
+
+// Normalize an authority per [HTTP Semantics §4.2.3](https://www.rfc-editor.org/rfc/rfc9110#section-4.2.3):
+// the host is lowercased, and the port is dropped when empty or the default for `scheme`.
+pub(crate) fn normalize_authority(scheme: &str, authority: &str) -> String {
+    let default_port = match scheme {
+        "http" => Some("80"),
+        "https" => Some("443"),
+        _ => None,
+    };
+
+    // Bracketed IPv6 literals contain colons, so only consider a colon following them.
+    let port_separator = authority
+        .rfind(':')
+        .filter(|index| !authority[*index..].contains(']'));
+
+    match port_separator {
+        Some(index) => {
+            let (host, port) = (&authority[..index], &authority[index + 1..]);
+            if port.is_empty() || Some(port) == default_port {
+                host.to_ascii_lowercase()
+            } else {
+                format!("{}:{port}", host.to_ascii_lowercase())
             }
         }
+        None => authority.to_ascii_lowercase(),
+    }
+}
+
+/// Compute the value of the [`@scheme`](https://www.rfc-editor.org/rfc/rfc9421#name-scheme)
+/// derived component, which must be lowercase, e.g. `https` rather than `HTTPS`.
+pub fn scheme_value(scheme: &str) -> String {
+    scheme.to_ascii_lowercase()
+}
+
+/// Compute the value of the [`@method`](https://www.rfc-editor.org/rfc/rfc9421#name-method)
+/// derived component. Method names are case-sensitive, and RFC 9421 forbids transforming them,
+/// yet some HTTP stacks report standard methods lowercased, e.g. `post`, which would never match
+/// a signer that covered `POST`. The methods defined by HTTP Semantics and `PATCH` are therefore
+/// uppercased, while extension methods are kept verbatim.
+pub fn method_value(method: &str) -> String {
+    const STANDARD_METHODS: [&str; 9] = [
+        "GET", "HEAD", "POST", "PUT", "DELETE", "CONNECT", "OPTIONS", "TRACE", "PATCH",
+    ];
+
+    STANDARD_METHODS
+        .iter()
+        .find(|standard| standard.eq_ignore_ascii_case(method))
+        .map_or_else(|| method.to_string(), |standard| (*standard).to_string())
+}
+
+/// Compute the value of the [`@target-uri`](https://www.rfc-editor.org/rfc/rfc9421#name-target-uri)
+/// derived component by reassembling the absolute target URI of a request. The scheme and host are
+/// lowercased, the port is dropped if it is the default for the scheme, `path` is kept encoded as
+/// in `path_value`, and `query` (without its leading `?`) is appended verbatim if present.
+pub fn target_uri_value(scheme: &str, authority: &str, path: &str, query: Option<&str>) -> String {
+    let scheme = scheme_value(scheme);
+    let authority = normalize_authority(&scheme, authority);
+    let path = path_value(path);
+
+    match query {
+        Some(query) => format!("{scheme}://{authority}{path}?{query}"),
+        None => format!("{scheme}://{authority}{path}"),
+    }
+}
+
+/// Compute the value of the [`@path`](https://www.rfc-editor.org/rfc/rfc9421#name-path) derived
+/// component from `path`, the absolute path of the target URI exactly as it appears on the wire,
+/// percent-encoding included. It must not be decoded nor otherwise normalized: `%2F` and `/`, or
+/// `%20` and a space, are different paths to the signature. An empty path is replaced by `/`.
+pub fn path_value(path: &str) -> String {
+    if path.is_empty() {
+        "/".to_string()
+    } else {
+        path.to_string()
+    }
+}
+
+/// Compute the value of the [`@query`](https://www.rfc-editor.org/rfc/rfc9421#name-query)
+/// derived component from `query`, the query of the target URI without its leading `?`. The
+/// value always begins with `?`: a query that is absent or empty, as in `/path` or `/path?`,
+/// both yield `?` alone, never an empty string.
+pub fn query_value(query: Option<&str>) -> String {
+    format!("?{}", query.unwrap_or_default())
+}
+
+/// Compute the value of the [`@request-target`](https://www.rfc-editor.org/rfc/rfc9421#name-request-target)
+/// derived component, i.e. the request target as it appears on the request line. This is the
+/// authority form for `CONNECT` requests, the asterisk form for `OPTIONS *` requests, and the
+/// origin form (`path`, kept encoded as in `path_value`, followed by `query` if present)
+/// otherwise. Requests sent in absolute form to a proxy should use `target_uri_value` instead.
+pub fn request_target_value(
+    method: &str,
+    authority: &str,
+    path: &str,
+    query: Option<&str>,
+) -> String {
+    if method == "CONNECT" {
+        return authority.to_string();
+    }
+
+    if method == "OPTIONS" && path == "*" && query.is_none() {
+        return "*".to_string();
+    }
+
+    let path = path_value(path);
+    match query {
+        Some(query) => format!("{path}?{query}"),
+        None => path,
     }
 }
 
@@ -460,6 +648,90 @@ pub enum CoveredComponent {
     Derived(DerivedComponent),
 }
 
+impl CoveredComponent {
+    /// Whether this component bears the `req` parameter, meaning its value is sourced from
+    /// the request that triggered the response being signed or verified.
+    pub fn is_request_bound(&self) -> bool {
+        match self {
+            CoveredComponent::HTTP(http) => http.parameters.0.contains(&HTTPFieldParameters::Req),
+            CoveredComponent::Derived(DerivedComponent::QueryParams { parameters }) => {
+                parameters.0.contains(&QueryParamParameters::Req)
+            }
+            CoveredComponent::Derived(
+                DerivedComponent::Authority { req }
+                | DerivedComponent::TargetUri { req }
+                | DerivedComponent::RequestTarget { req }
+                | DerivedComponent::Method { req }
+                | DerivedComponent::Path { req }
+                | DerivedComponent::Scheme { req }
+                | DerivedComponent::Query { req }
+                | DerivedComponent::Status { req },
+            ) => *req,
+        }
+    }
+
+    /// Whether this component bears the `tr` parameter, meaning its value must be sourced
+    /// from the trailer section of the message rather than its header section. Only HTTP
+    /// fields may bear it.
+    pub fn is_trailer(&self) -> bool {
+        match self {
+            CoveredComponent::HTTP(http) => http.parameters.0.contains(&HTTPFieldParameters::Tr),
+            CoveredComponent::Derived(_) => false,
+        }
+    }
+
+    /// Obtain a copy of this component without the `req` parameter, i.e. the component as
+    /// it would be referenced on the request itself.
+    pub fn without_req(&self) -> CoveredComponent {
+        match self {
+            CoveredComponent::HTTP(http) => CoveredComponent::HTTP(HTTPField {
+                name: http.name.clone(),
+                parameters: HTTPFieldParametersSet(
+                    http.parameters
+                        .0
+                        .iter()
+                        .filter(|param| **param != HTTPFieldParameters::Req)
+                        .cloned()
+                        .collect(),
+                ),
+            }),
+            CoveredComponent::Derived(derived) => CoveredComponent::Derived(match derived {
+                DerivedComponent::Authority { .. } => DerivedComponent::Authority { req: false },
+                DerivedComponent::TargetUri { .. } => DerivedComponent::TargetUri { req: false },
+                DerivedComponent::RequestTarget { .. } => {
+                    DerivedComponent::RequestTarget { req: false }
+                }
+                DerivedComponent::Method { .. } => DerivedComponent::Method { req: false },
+                DerivedComponent::Path { .. } => DerivedComponent::Path { req: false },
+                DerivedComponent::Scheme { .. } => DerivedComponent::Scheme { req: false },
+                DerivedComponent::Query { .. } => DerivedComponent::Query { req: false },
+                DerivedComponent::Status { .. } => DerivedComponent::Status { req: false },
+                DerivedComponent::QueryParams { parameters } => DerivedComponent::QueryParams {
+                    parameters: QueryParamParametersSet(
+                        parameters
+                            .0
+                            .iter()
+                            .filter(|param| **param != QueryParamParameters::Req)
+                            .cloned()
+                            .collect(),
+                    ),
+                },
+            }),
+        }
+    }
+}
+
+impl fmt::Display for CoveredComponent {
+    /// Renders the component identifier, as found in `Signature-Input` and signature bases,
+    /// e.g. `"@authority"` or `"example-dict";key="a"`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoveredComponent::HTTP(http) => http.fmt(f),
+            CoveredComponent::Derived(derived) => derived.fmt(f),
+        }
+    }
+}
+
 impl TryFrom<sfv::Item> for CoveredComponent {
     type Error = ImplementationError;
 
@@ -548,6 +820,113 @@ impl TryFrom<sfv::Item> for CoveredComponent {
     }
 }
 
+/// Fluent builder for the covered components of a message, as returned by
+/// `UnsignedMessage::fetch_components_to_cover`. Components are covered in the order they
+/// are added, which is the order they appear in the signature base. Adding a component a
+/// second time replaces its value but keeps its original position. Derived components are
+/// added without the `req` parameter; use `component` for anything else.
+#[derive(Clone, Debug, Default)]
+pub struct ComponentsBuilder {
+    components: IndexMap<CoveredComponent, String>,
+}
+
+impl ComponentsBuilder {
+    /// Start an empty list of covered components.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cover an arbitrary `component` with `value`.
+    #[must_use]
+    pub fn component(mut self, component: CoveredComponent, value: impl Into<String>) -> Self {
+        self.components.insert(component, value.into());
+        self
+    }
+
+    fn derived(self, component: DerivedComponent, value: impl Into<String>) -> Self {
+        self.component(CoveredComponent::Derived(component), value)
+    }
+
+    /// Cover `@authority`.
+    #[must_use]
+    pub fn authority(self, value: impl Into<String>) -> Self {
+        self.derived(DerivedComponent::Authority { req: false }, value)
+    }
+
+    /// Cover `@target-uri`, see `target_uri_value`.
+    #[must_use]
+    pub fn target_uri(self, value: impl Into<String>) -> Self {
+        self.derived(DerivedComponent::TargetUri { req: false }, value)
+    }
+
+    /// Cover `@request-target`, see `request_target_value`.
+    #[must_use]
+    pub fn request_target(self, value: impl Into<String>) -> Self {
+        self.derived(DerivedComponent::RequestTarget { req: false }, value)
+    }
+
+    /// Cover `@method`, see `method_value`.
+    #[must_use]
+    pub fn method(self, value: impl AsRef<str>) -> Self {
+        self.derived(
+            DerivedComponent::Method { req: false },
+            method_value(value.as_ref()),
+        )
+    }
+
+    /// Cover `@path`, see `path_value`.
+    #[must_use]
+    pub fn path(self, value: impl Into<String>) -> Self {
+        self.derived(DerivedComponent::Path { req: false }, value)
+    }
+
+    /// Cover `@scheme`, see `scheme_value`.
+    #[must_use]
+    pub fn scheme(self, value: impl Into<String>) -> Self {
+        self.derived(DerivedComponent::Scheme { req: false }, value)
+    }
+
+    /// Cover `@query`, see `query_value`.
+    #[must_use]
+    pub fn query(self, value: impl Into<String>) -> Self {
+        self.derived(DerivedComponent::Query { req: false }, value)
+    }
+
+    /// Cover the `@query-param` named `name`.
+    #[must_use]
+    pub fn query_param(self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.derived(
+            DerivedComponent::QueryParams {
+                parameters: QueryParamParametersSet(vec![QueryParamParameters::Name(name.into())]),
+            },
+            value,
+        )
+    }
+
+    /// Cover `@status`, see `status_value`.
+    #[must_use]
+    pub fn status(self, value: impl Into<String>) -> Self {
+        self.derived(DerivedComponent::Status { req: false }, value)
+    }
+
+    /// Cover the HTTP field `name`, lowercased as required, see `http_field_value`.
+    #[must_use]
+    pub fn header(self, name: &str, value: impl Into<String>) -> Self {
+        self.component(
+            CoveredComponent::HTTP(HTTPField {
+                name: name.to_ascii_lowercase(),
+                parameters: HTTPFieldParametersSet(vec![]),
+            }),
+            value,
+        )
+    }
+
+    /// Obtain the covered components, in the order they were added.
+    pub fn build(self) -> IndexMap<CoveredComponent, String> {
+        self.components
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use sfv::SerializeValue;
@@ -736,4 +1115,328 @@ mod tests {
                 .expect_err("This case should error");
         }
     }
+
+    #[test]
+    fn test_status_value() {
+        assert_eq!(status_value(200).unwrap(), "200");
+        assert_eq!(status_value(404).unwrap(), "404");
+        assert_eq!(status_value(100).unwrap(), "100");
+        for invalid in [0, 99, 1000, u16::MAX] {
+            status_value(invalid).expect_err("This case should error");
+        }
+    }
+
+    #[test]
+    fn test_stripping_req_parameter() {
+        for (case, request_bound, expected) in [
+            (r#""@authority";req"#, true, r#""@authority""#),
+            (r#""@authority""#, false, r#""@authority""#),
+            (
+                r#""@query-param";name="foo";req"#,
+                true,
+                r#""@query-param";name="foo""#,
+            ),
+            (r#""content-type";req;sf"#, true, r#""content-type";sf"#),
+            (
+                r#""content-type";key="foo""#,
+                false,
+                r#""content-type";key="foo""#,
+            ),
+        ] {
+            let component: CoveredComponent = sfv::Parser::new(case)
+                .parse_item()
+                .unwrap()
+                .try_into()
+                .unwrap();
+            assert_eq!(component.is_request_bound(), request_bound);
+            let stripped = component.without_req();
+            assert!(!stripped.is_request_bound());
+            let value: sfv::Item = match stripped {
+                CoveredComponent::HTTP(http) => http.try_into().unwrap(),
+                CoveredComponent::Derived(derived) => derived.try_into().unwrap(),
+            };
+            assert_eq!(value.serialize_value(), expected);
+        }
+    }
+
+    #[test]
+    fn test_target_uri_value() {
+        for (scheme, authority, path, query, expected) in [
+            (
+                "https",
+                "www.example.com",
+                "/path",
+                Some("param=value"),
+                "https://www.example.com/path?param=value",
+            ),
+            (
+                "http",
+                "www.example.com:80",
+                "/path",
+                None,
+                "http://www.example.com/path",
+            ),
+            (
+                "http",
+                "www.example.com:8080",
+                "/path",
+                None,
+                "http://www.example.com:8080/path",
+            ),
+            (
+                "https",
+                "www.example.com:80",
+                "/",
+                None,
+                "https://www.example.com:80/",
+            ),
+            (
+                "HTTPS",
+                "WWW.Example.com:443",
+                "",
+                Some(""),
+                "https://www.example.com/?",
+            ),
+            ("https", "[::1]:443", "/a", None, "https://[::1]/a"),
+            ("https", "[::1]:8443", "/a", None, "https://[::1]:8443/a"),
+        ] {
+            assert_eq!(target_uri_value(scheme, authority, path, query), expected);
+        }
+    }
+
+    #[test]
+    fn test_path_value_preserves_encoding() {
+        let path = "/files/my%20report%2Fdraft";
+        assert_eq!(path_value(path), path);
+        assert_eq!(path_value(""), "/");
+        assert_eq!(
+            target_uri_value("https", "example.com", path, None),
+            "https://example.com/files/my%20report%2Fdraft"
+        );
+        assert_eq!(
+            request_target_value("GET", "example.com", path, Some("a=%2F")),
+            "/files/my%20report%2Fdraft?a=%2F"
+        );
+    }
+
+    #[test]
+    fn test_method_value() {
+        assert_eq!(method_value("post"), "POST");
+        assert_eq!(method_value("Get"), "GET");
+        assert_eq!(method_value("PATCH"), "PATCH");
+        // Extension methods are case-sensitive, and left alone.
+        assert_eq!(method_value("purge"), "purge");
+        assert_eq!(method_value("PROPFIND"), "PROPFIND");
+    }
+
+    #[test]
+    fn test_query_value() {
+        // https://www.rfc-editor.org/rfc/rfc9421#name-query
+        assert_eq!(
+            query_value(Some("param=value&foo=bar&baz=bat%2Dman")),
+            "?param=value&foo=bar&baz=bat%2Dman"
+        );
+        assert_eq!(query_value(Some("")), "?");
+        assert_eq!(query_value(None), "?");
+    }
+
+    #[test]
+    fn test_request_target_value() {
+        for (method, authority, path, query, expected) in [
+            (
+                "GET",
+                "www.example.com",
+                "/path",
+                Some("param=value"),
+                "/path?param=value",
+            ),
+            ("GET", "www.example.com", "", None, "/"),
+            ("POST", "www.example.com", "/path", Some(""), "/path?"),
+            ("OPTIONS", "www.example.com", "*", None, "*"),
+            ("OPTIONS", "www.example.com", "/path", None, "/path"),
+            (
+                "CONNECT",
+                "www.example.com:80",
+                "",
+                None,
+                "www.example.com:80",
+            ),
+        ] {
+            assert_eq!(
+                request_target_value(method, authority, path, query),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_http_field_value() {
+        let fields = [
+            ("Content-Length", " 18 "),
+            ("X-Folded", "first\r\n  second"),
+            ("cache-control", "max-age=60"),
+            ("Cache-Control", "must-revalidate"),
+        ];
+        assert_eq!(
+            http_field_value(fields, "content-length"),
+            Some("18".to_string())
+        );
+        assert_eq!(
+            http_field_value(fields, "x-folded"),
+            Some("first second".to_string())
+        );
+        assert_eq!(
+            http_field_value(fields, "cache-control"),
+            Some("max-age=60, must-revalidate".to_string())
+        );
+        assert_eq!(http_field_value(fields, "content-type"), None);
+    }
+
+    #[test]
+    fn test_combine_field_values() {
+        // https://www.rfc-editor.org/rfc/rfc9421#name-http-fields
+        assert_eq!(
+            combine_field_values(&["   Leading and trailing whitespace.   "]),
+            "Leading and trailing whitespace."
+        );
+        assert_eq!(
+            combine_field_values(&["value, with, lots", "of, commas"]),
+            "value, with, lots, of, commas"
+        );
+        assert_eq!(
+            combine_field_values(&["Obsolete\r\n    line folding."]),
+            "Obsolete line folding."
+        );
+        assert_eq!(
+            combine_field_values(&["\tfirst ", " second\n\t third"]),
+            "first, second third"
+        );
+        assert_eq!(combine_field_values(&[""]), "");
+    }
+
+    #[test]
+    fn test_components_builder() {
+        // As in `examples/rust/signing.rs`
+        assert_eq!(
+            ComponentsBuilder::new().authority("example.com").build(),
+            IndexMap::<_, _>::from_iter([(
+                CoveredComponent::Derived(DerivedComponent::Authority { req: false }),
+                "example.com".to_string(),
+            )])
+        );
+
+        let built = ComponentsBuilder::new()
+            .method("POST")
+            .authority("example.com")
+            .path("/api/items")
+            .query_param("page", "2")
+            .header("Content-Length", "18")
+            .build();
+        let manual = IndexMap::<_, _>::from_iter([
+            (
+                CoveredComponent::Derived(DerivedComponent::Method { req: false }),
+                "POST".to_string(),
+            ),
+            (
+                CoveredComponent::Derived(DerivedComponent::Authority { req: false }),
+                "example.com".to_string(),
+            ),
+            (
+                CoveredComponent::Derived(DerivedComponent::Path { req: false }),
+                "/api/items".to_string(),
+            ),
+            (
+                CoveredComponent::Derived(DerivedComponent::QueryParams {
+                    parameters: QueryParamParametersSet(vec![QueryParamParameters::Name(
+                        "page".into(),
+                    )]),
+                }),
+                "2".to_string(),
+            ),
+            (
+                CoveredComponent::HTTP(HTTPField {
+                    name: "content-length".into(),
+                    parameters: HTTPFieldParametersSet(vec![]),
+                }),
+                "18".to_string(),
+            ),
+        ]);
+        assert_eq!(built, manual);
+        // Order is significant, and preserved.
+        assert!(built.keys().eq(manual.keys()));
+
+        let replaced = ComponentsBuilder::new()
+            .method("GET")
+            .authority("example.com")
+            .method("POST")
+            .build();
+        assert_eq!(
+            replaced.first(),
+            Some((
+                &CoveredComponent::Derived(DerivedComponent::Method { req: false }),
+                &"POST".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_displaying_components() {
+        for identifier in [
+            r#""@authority""#,
+            r#""@authority";req"#,
+            r#""@query-param";name="a""#,
+            r#""content-length""#,
+            r#""example-dict";key="a""#,
+            r#""example-dict";sf;req"#,
+        ] {
+            let item = sfv::Parser::new(identifier).parse_item().unwrap();
+            let component = CoveredComponent::try_from(item.clone()).unwrap();
+            assert_eq!(component.to_string(), identifier);
+            assert_eq!(component.to_string(), item.serialize_value());
+        }
+
+        let invalid = HTTPField {
+            name: "caf\u{e9}".into(),
+            parameters: HTTPFieldParametersSet(vec![]),
+        };
+        assert_eq!(invalid.to_string(), "\"caf\u{e9}\"");
+    }
+
+    #[test]
+    fn test_query_param_parameter_order_is_preserved() {
+        for identifier in [
+            r#""@query-param";name="a";req"#,
+            r#""@query-param";req;name="a""#,
+        ] {
+            let item = sfv::Parser::new(identifier).parse_item().unwrap();
+            let component = CoveredComponent::try_from(item).unwrap();
+            assert!(component.is_request_bound());
+            let CoveredComponent::Derived(derived) = &component else {
+                panic!("`@query-param` should parse as a derived component");
+            };
+            assert_eq!(
+                sfv::Item::try_from(derived.clone())
+                    .unwrap()
+                    .serialize_value(),
+                identifier
+            );
+            assert_eq!(component.to_string(), identifier);
+        }
+
+        let name_first = DerivedComponent::QueryParams {
+            parameters: QueryParamParametersSet(vec![
+                QueryParamParameters::Name("a".into()),
+                QueryParamParameters::Req,
+            ]),
+        };
+        let req_first = DerivedComponent::QueryParams {
+            parameters: QueryParamParametersSet(vec![
+                QueryParamParameters::Req,
+                QueryParamParameters::Name("a".into()),
+            ]),
+        };
+        assert_eq!(name_first.to_string(), r#""@query-param";name="a";req"#);
+        assert_eq!(req_first.to_string(), r#""@query-param";req;name="a""#);
+        assert_ne!(name_first, req_first);
+    }
 }
@@ -0,0 +1,173 @@
+// Copyright (c) 2025 Cloudflare, Inc.
+// Licensed under the Apache 2.0 license found in the LICENSE file or at:
+//     https://opensource.org/licenses/Apache-2.0
+
+use super::{ImplementationError, primitives};
+use sfv::SerializeValue;
+use sha2::Digest;
+
+/// Hash algorithms usable in a [`Content-Digest`](https://www.rfc-editor.org/rfc/rfc9530)
+/// header. Ordered from weakest to strongest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DigestAlgorithm {
+    /// `sha-256`
+    Sha256,
+    /// `sha-512`
+    Sha512,
+}
+
+impl DigestAlgorithm {
+    /// The key identifying this algorithm in a `Content-Digest` dictionary.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DigestAlgorithm::Sha256 => "sha-256",
+            DigestAlgorithm::Sha512 => "sha-512",
+        }
+    }
+
+    /// Hash `body` with this algorithm.
+    pub fn digest(&self, body: &[u8]) -> Vec<u8> {
+        match self {
+            DigestAlgorithm::Sha256 => sha2::Sha256::digest(body).to_vec(),
+            DigestAlgorithm::Sha512 => sha2::Sha512::digest(body).to_vec(),
+        }
+    }
+}
+
+impl std::str::FromStr for DigestAlgorithm {
+    type Err = ImplementationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sha-256" => Ok(DigestAlgorithm::Sha256),
+            "sha-512" => Ok(DigestAlgorithm::Sha512),
+            _ => Err(ImplementationError::ParsingError(format!(
+                "Unsupported content digest algorithm `{s}`"
+            ))),
+        }
+    }
+}
+
+/// Compute the value of a `Content-Digest` header for `body`, carrying one digest per entry
+/// of `algorithms`, in that order, e.g. `sha-256=:...:, sha-512=:...:`. To be covered as the
+/// `content-digest` component.
+pub fn content_digest_value(body: &[u8], algorithms: &[DigestAlgorithm]) -> String {
+    let dictionary: sfv::Dictionary = algorithms
+        .iter()
+        .map(|algorithm| {
+            (
+                sfv::KeyRef::constant(algorithm.as_str()).to_owned(),
+                sfv::ListEntry::Item(sfv::Item::new(sfv::BareItem::ByteSequence(
+                    algorithm.digest(body),
+                ))),
+            )
+        })
+        .collect();
+
+    dictionary.serialize_value().unwrap_or_default()
+}
+
+/// Check the `Content-Digest` header value `content_digest` against `body`. Only the digest of
+/// the strongest supported algorithm present is checked, and returned on success; digests of
+/// unsupported algorithms are ignored.
+///
+/// # Errors
+///
+/// Returns `ImplementationError::ParsingError` if `content_digest` is malformed or carries no
+/// supported algorithm, and `ImplementationError::ContentDigestMismatch` if the digest differs.
+pub fn verify_content_digest(
+    content_digest: &str,
+    body: &[u8],
+) -> Result<DigestAlgorithm, ImplementationError> {
+    let dictionary = sfv::Parser::new(content_digest)
+        .parse_dictionary()
+        .map_err(|e| {
+            ImplementationError::ParsingError(format!(
+                "Failed to parse `Content-Digest` header into sfv::Dictionary: {e}"
+            ))
+        })?;
+
+    let (algorithm, expected) = dictionary
+        .iter()
+        .filter_map(|(key, entry)| {
+            let algorithm = key.as_str().parse::<DigestAlgorithm>().ok()?;
+            Some((algorithm, entry))
+        })
+        .max_by_key(|(algorithm, _)| *algorithm)
+        .ok_or(ImplementationError::ParsingError(
+            "`Content-Digest` carries no supported algorithm".into(),
+        ))?;
+
+    let expected = match expected {
+        sfv::ListEntry::Item(sfv::Item {
+            bare_item: sfv::BareItem::ByteSequence(digest),
+            ..
+        }) => digest,
+        _ => {
+            return Err(ImplementationError::ParsingError(format!(
+                "`{}` digest must be a byte sequence",
+                algorithm.as_str()
+            )));
+        }
+    };
+
+    if primitives::ct_eq(&algorithm.digest(body), expected) {
+        Ok(algorithm)
+    } else {
+        Err(ImplementationError::ContentDigestMismatch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_digest_value() {
+        // https://www.rfc-editor.org/rfc/rfc9530#appendix-D.1
+        assert_eq!(
+            content_digest_value(b"{\"hello\": \"world\"}\n", &[DigestAlgorithm::Sha256]),
+            "sha-256=:RK/0qy18MlBSVnWgjwz6lZEWjP/lF5HF9bvEF8FabDg=:"
+        );
+        assert_eq!(
+            content_digest_value(b"{\"hello\": \"world\"}\n", &[DigestAlgorithm::Sha512]),
+            "sha-512=:YMAam51Jz/jOATT6/zvHrLVgOYTGFy1d6GJiOHTohq4yP+pgk4vf2aCsyRZOtw8MjkM7iw7yZ/WkppmM44T3qg==:"
+        );
+        assert!(content_digest_value(b"", &[]).is_empty());
+    }
+
+    #[test]
+    fn test_verifying_the_strongest_digest() {
+        let body = b"{\"hello\": \"world\"}\n";
+        let both = content_digest_value(body, &[DigestAlgorithm::Sha256, DigestAlgorithm::Sha512]);
+        assert!(both.starts_with("sha-256=:") && both.contains(", sha-512=:"));
+        assert_eq!(
+            verify_content_digest(&both, body).unwrap(),
+            DigestAlgorithm::Sha512
+        );
+        assert_eq!(
+            verify_content_digest(
+                &content_digest_value(body, &[DigestAlgorithm::Sha256]),
+                body
+            )
+            .unwrap(),
+            DigestAlgorithm::Sha256
+        );
+
+        // A valid `sha-256` does not rescue a mismatching `sha-512`.
+        let mixed = format!(
+            "{}, {}, md5=:AAAA:",
+            content_digest_value(body, &[DigestAlgorithm::Sha256]),
+            content_digest_value(b"tampered", &[DigestAlgorithm::Sha512])
+        );
+        assert!(matches!(
+            verify_content_digest(&mixed, body),
+            Err(ImplementationError::ContentDigestMismatch)
+        ));
+
+        assert!(matches!(
+            verify_content_digest("md5=:AAAA:", body),
+            Err(ImplementationError::ParsingError(_))
+        ));
+    }
+}
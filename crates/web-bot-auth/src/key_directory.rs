@@ -0,0 +1,751 @@
+// Copyright (c) 2025 Cloudflare, Inc.
+// Licensed under the Apache 2.0 license found in the LICENSE file or at:
+//     https://opensource.org/licenses/Apache-2.0
+
+use super::{
+    Clock, ImplementationError, KeyRing, MessageVerifier, SignatureTiming, SystemClock, Thumbprint,
+    components,
+};
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+/// Retrieves the keys published by a key directory, such as a JWKS served at
+/// `/.well-known/http-message-signatures-directory`. Implementations own the HTTP client,
+/// which keeps this crate free of one and makes them easy to mock in tests, and may hand the
+/// response body to `keyring_from_directory`. Links listed in `Signature-Agent` are handed over
+/// as they are, once normalized, including `data:` URIs embedding a directory, which
+/// implementations must decode themselves rather than fetch, or reject.
+pub trait KeyDirectoryFetcher {
+    /// Fetch the directory at `url`, returning its keys indexed by JWK thumbprint.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ImplementationErrors` if the directory cannot be fetched or parsed.
+    fn fetch(&self, url: &str) -> Result<KeyRing, ImplementationError>;
+}
+
+/// Extract the keys of a Web Bot Auth key directory, as served at
+/// `/.well-known/http-message-signatures-directory`, indexed by their RFC 7638 JWK thumbprint.
+/// The directory is JWKS-shaped: a JSON object whose top-level `keys` member is an array of
+/// JWKs. Keys nested deeper, say under a per-agent member, are not looked for. Unknown members,
+/// of the directory or of its keys, are ignored, as are keys this crate can't verify with, so
+/// that a directory also publishing, say, RSA keys still yields its Ed25519 ones. Ed25519 keys
+/// whose `x` is not a base64url-encoded 32-byte public key are skipped alike.
+///
+/// As directories are fetched from untrusted origins, documents longer than
+/// `MAX_DIRECTORY_LENGTH` bytes, or listing more than `MAX_DIRECTORY_KEYS` keys, are rejected.
+///
+/// # Errors
+///
+/// Returns `ImplementationError::InputTooLarge` if `document` exceeds one of the limits above,
+/// and `ImplementationError::ParsingError` if it is not JSON or has no `keys` array.
+pub fn keyring_from_directory(document: &str) -> Result<KeyRing, ImplementationError> {
+    if document.len() > MAX_DIRECTORY_LENGTH {
+        return Err(ImplementationError::InputTooLarge(format!(
+            "key directory is {} bytes long, exceeding the limit of {MAX_DIRECTORY_LENGTH}",
+            document.len()
+        )));
+    }
+    let directory = JsonParser::new(document).parse_document()?;
+    let keys = match directory.member("keys") {
+        Some(Json::Array(keys)) => keys,
+        _ => {
+            return Err(ImplementationError::ParsingError(
+                "Key directory must be a JSON object with a `keys` array".into(),
+            ));
+        }
+    };
+    if keys.len() > MAX_DIRECTORY_KEYS {
+        return Err(ImplementationError::InputTooLarge(format!(
+            "key directory lists {} keys, exceeding the limit of {MAX_DIRECTORY_KEYS}",
+            keys.len()
+        )));
+    }
+
+    let mut keyring = KeyRing::default();
+    for key in keys {
+        let (Some(Json::String(kty)), Some(Json::String(crv)), Some(Json::String(x))) =
+            (key.member("kty"), key.member("crv"), key.member("x"))
+        else {
+            continue;
+        };
+        if kty != "OKP" || crv != "Ed25519" {
+            continue;
+        }
+        let Some(public_key) = URL_SAFE_NO_PAD
+            .decode(x.trim_end_matches('='))
+            .ok()
+            .filter(|public_key| public_key.len() == ed25519_dalek::PUBLIC_KEY_LENGTH)
+        else {
+            continue;
+        };
+        keyring.insert(ed25519_thumbprint(&public_key), public_key);
+    }
+    Ok(keyring)
+}
+
+// The RFC 7638 thumbprint of an Ed25519 JWK, over its required members in lexicographic order.
+fn ed25519_thumbprint(public_key: &[u8]) -> Thumbprint {
+    let canonical = format!(
+        r#"{{"crv":"Ed25519","kty":"OKP","x":"{}"}}"#,
+        URL_SAFE_NO_PAD.encode(public_key)
+    );
+    URL_SAFE_NO_PAD.encode(Sha256::digest(canonical.as_bytes()))
+}
+
+/// The longest key directory document `keyring_from_directory` accepts, in bytes.
+pub const MAX_DIRECTORY_LENGTH: usize = 64 * 1024;
+
+/// The most keys a key directory document may list for `keyring_from_directory` to accept it.
+pub const MAX_DIRECTORY_KEYS: usize = 64;
+
+// Nesting beyond this is rejected, as directories are fetched from untrusted origins.
+const MAX_JSON_DEPTH: usize = 32;
+
+// Just enough JSON to read a key directory. Numbers are validated but not kept, as no member
+// this crate reads is numeric.
+#[derive(Debug, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number,
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn member(&self, name: &str) -> Option<&Json> {
+        match self {
+            Json::Object(members) => members
+                .iter()
+                .find(|(member, _)| member == name)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+}
+
+struct JsonParser<'a> {
+    input: &'a [u8],
+    position: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            input: input.as_bytes(),
+            position: 0,
+        }
+    }
+
+    fn error(&self, reason: &str) -> ImplementationError {
+        ImplementationError::ParsingError(format!(
+            "Invalid key directory JSON at byte {}: {reason}",
+            self.position
+        ))
+    }
+
+    fn parse_document(mut self) -> Result<Json, ImplementationError> {
+        let value = self.parse_value(0)?;
+        self.skip_whitespace();
+        if self.position != self.input.len() {
+            return Err(self.error("trailing characters"));
+        }
+        Ok(value)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.position += 1;
+        Some(byte)
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), ImplementationError> {
+        self.skip_whitespace();
+        if self.next() == Some(expected) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", expected as char)))
+        }
+    }
+
+    fn parse_literal(&mut self, literal: &str, value: Json) -> Result<Json, ImplementationError> {
+        if self.input[self.position..].starts_with(literal.as_bytes()) {
+            self.position += literal.len();
+            Ok(value)
+        } else {
+            Err(self.error("unexpected token"))
+        }
+    }
+
+    fn parse_value(&mut self, depth: usize) -> Result<Json, ImplementationError> {
+        if depth > MAX_JSON_DEPTH {
+            return Err(self.error("nested too deeply"));
+        }
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => self.parse_object(depth),
+            Some(b'[') => self.parse_array(depth),
+            Some(b'"') => self.parse_string().map(Json::String),
+            Some(b't') => self.parse_literal("true", Json::Bool(true)),
+            Some(b'f') => self.parse_literal("false", Json::Bool(false)),
+            Some(b'n') => self.parse_literal("null", Json::Null),
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    fn parse_object(&mut self, depth: usize) -> Result<Json, ImplementationError> {
+        self.expect(b'{')?;
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.position += 1;
+            return Ok(Json::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some(b'"') {
+                return Err(self.error("expected a member name"));
+            }
+            let name = self.parse_string()?;
+            self.expect(b':')?;
+            members.push((name, self.parse_value(depth + 1)?));
+            self.skip_whitespace();
+            match self.next() {
+                Some(b',') => continue,
+                Some(b'}') => return Ok(Json::Object(members)),
+                _ => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+    }
+
+    fn parse_array(&mut self, depth: usize) -> Result<Json, ImplementationError> {
+        self.expect(b'[')?;
+        let mut elements = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.position += 1;
+            return Ok(Json::Array(elements));
+        }
+        loop {
+            elements.push(self.parse_value(depth + 1)?);
+            self.skip_whitespace();
+            match self.next() {
+                Some(b',') => continue,
+                Some(b']') => return Ok(Json::Array(elements)),
+                _ => return Err(self.error("expected `,` or `]`")),
+            }
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Json, ImplementationError> {
+        let start = self.position;
+        while let Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') = self.peek() {
+            self.position += 1;
+        }
+        core::str::from_utf8(&self.input[start..self.position])
+            .ok()
+            .and_then(|number| number.parse::<f64>().ok())
+            .map(|_| Json::Number)
+            .ok_or(self.error("invalid number"))
+    }
+
+    fn parse_string(&mut self) -> Result<String, ImplementationError> {
+        self.expect(b'"')?;
+        let mut bytes = Vec::new();
+        loop {
+            match self.next() {
+                Some(b'"') => break,
+                Some(b'\\') => match self.next() {
+                    Some(b'"') => bytes.push(b'"'),
+                    Some(b'\\') => bytes.push(b'\\'),
+                    Some(b'/') => bytes.push(b'/'),
+                    Some(b'b') => bytes.push(0x08),
+                    Some(b'f') => bytes.push(0x0c),
+                    Some(b'n') => bytes.push(b'\n'),
+                    Some(b'r') => bytes.push(b'\r'),
+                    Some(b't') => bytes.push(b'\t'),
+                    Some(b'u') => {
+                        let character = self.parse_escaped_char()?;
+                        let mut buffer = [0; 4];
+                        bytes.extend_from_slice(character.encode_utf8(&mut buffer).as_bytes());
+                    }
+                    _ => return Err(self.error("invalid escape")),
+                },
+                Some(0x00..=0x1f) | None => return Err(self.error("unterminated string")),
+                Some(byte) => bytes.push(byte),
+            }
+        }
+        String::from_utf8(bytes).map_err(|_| self.error("string is not UTF-8"))
+    }
+
+    // The character of a `\u` escape, whose `\u` has been consumed, including surrogate pairs.
+    fn parse_escaped_char(&mut self) -> Result<char, ImplementationError> {
+        let high = self.parse_hex4()?;
+        let code_point = if (0xd800..0xdc00).contains(&high) {
+            if self.next() != Some(b'\\') || self.next() != Some(b'u') {
+                return Err(self.error("unpaired surrogate"));
+            }
+            let low = self.parse_hex4()?;
+            if !(0xdc00..0xe000).contains(&low) {
+                return Err(self.error("unpaired surrogate"));
+            }
+            0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+        } else {
+            high
+        };
+        char::from_u32(code_point).ok_or(self.error("unpaired surrogate"))
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, ImplementationError> {
+        let digits = self
+            .input
+            .get(self.position..self.position + 4)
+            .and_then(|digits| core::str::from_utf8(digits).ok())
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .ok_or(self.error("invalid unicode escape"))?;
+        self.position += 4;
+        Ok(digits)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct CachedDirectory {
+    fetched_at: Duration,
+    keyring: Arc<KeyRing>,
+    // Value of `KeyDirectoryCache::uses` when last read, to evict the least recently used.
+    last_used: u64,
+}
+
+// Normalize `url` so that spellings of the same directory share a cache entry: the scheme and
+// host are lowercased, and the default port, any userinfo and the fragment are dropped. Links
+// that aren't hierarchical, such as `data:` URIs, are kept as they are.
+fn normalize_url(url: &str) -> String {
+    let url = url.trim();
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return url.to_string();
+    };
+    let scheme = components::scheme_value(scheme);
+    let rest = rest.split('#').next().unwrap_or_default();
+    let (authority, path_and_query) = rest.split_at(rest.find(['/', '?']).unwrap_or(rest.len()));
+    let authority = authority.rsplit('@').next().unwrap_or_default();
+    let path_and_query = match path_and_query {
+        "" => "/",
+        path_and_query => path_and_query,
+    };
+
+    format!(
+        "{scheme}://{}{path_and_query}",
+        components::normalize_authority(&scheme, authority)
+    )
+}
+
+/// A cache of key directories in front of a `KeyDirectoryFetcher`, so that high-traffic
+/// verifiers don't fetch a directory for every message. A directory is fetched again once it
+/// is older than the configured TTL, or when a message references a key it doesn't hold,
+/// which happens on key rotation. As unknown `keyid`s are attacker controlled, the latter is
+/// limited to once per `min_refresh_interval`. The cache can be shared across threads.
+///
+/// Directories are keyed by their normalized URL. As messages choose the URLs, the cache holds
+/// at most `max_entries` directories, evicting the least recently used beyond that, so that a
+/// sender varying the path or query of its links cannot grow it without bound.
+#[derive(Debug)]
+pub struct KeyDirectoryCache<F> {
+    fetcher: F,
+    ttl: Duration,
+    min_refresh_interval: Duration,
+    max_entries: usize,
+    clock: Arc<dyn Clock>,
+    directories: Mutex<HashMap<String, CachedDirectory>>,
+    uses: AtomicU64,
+}
+
+impl<F: KeyDirectoryFetcher> KeyDirectoryCache<F> {
+    /// Create an empty cache fetching directories with `fetcher`, and keeping them for `ttl`.
+    /// Directories are refreshed on a missing key at most once a minute, at most 1024 of them
+    /// are kept, and time is read from `SystemClock`.
+    pub fn new(fetcher: F, ttl: Duration) -> Self {
+        Self {
+            fetcher,
+            ttl,
+            min_refresh_interval: Duration::from_secs(60),
+            max_entries: 1024,
+            clock: Arc::new(SystemClock),
+            directories: Mutex::new(HashMap::new()),
+            uses: AtomicU64::new(0),
+        }
+    }
+
+    /// Keep at most `max_entries` directories, evicting the least recently used beyond that.
+    /// At least one directory is always kept.
+    #[must_use]
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries.max(1);
+        self
+    }
+
+    /// Read the current time from `clock` rather than the system clock.
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Refresh a directory because of a missing key at most once per `interval`.
+    #[must_use]
+    pub fn with_min_refresh_interval(mut self, interval: Duration) -> Self {
+        self.min_refresh_interval = interval;
+        self
+    }
+
+    /// Obtain the keys published at `url`, fetching them if they are absent from the cache
+    /// or older than the TTL. `url` is normalized first, and handed to the fetcher as such.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ImplementationErrors` raised by the fetcher or the clock.
+    pub fn keyring(&self, url: &str) -> Result<Arc<KeyRing>, ImplementationError> {
+        let url = normalize_url(url);
+        let now = self.clock.now()?;
+        match self.cached(&url) {
+            Some(cached) if now.saturating_sub(cached.fetched_at) < self.ttl => Ok(cached.keyring),
+            _ => self.refresh(&url, now),
+        }
+    }
+
+    /// Drop `url` from the cache, so that it is fetched again on next use.
+    pub fn invalidate(&self, url: &str) {
+        self.directories
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&normalize_url(url));
+    }
+
+    /// The number of directories currently cached.
+    pub fn len(&self) -> usize {
+        self.directories
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Whether no directory is currently cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Verify `verifier` against the keys published at `url`, as `MessageVerifier::verify`
+    /// would. If the key is missing from a cached copy of the directory, it is fetched again
+    /// once, subject to `min_refresh_interval`, before giving up.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ImplementationErrors` raised by the fetcher, as well as those relevant to
+    /// verifying.
+    pub fn verify(
+        &self,
+        url: &str,
+        verifier: &MessageVerifier,
+        key_id: Option<Thumbprint>,
+    ) -> Result<SignatureTiming, ImplementationError> {
+        let keyring = self.keyring(url)?;
+        match verifier.verify(&keyring, key_id.clone()) {
+            Err(ImplementationError::NoSuchKey) => {
+                let url = normalize_url(url);
+                let now = self.clock.now()?;
+                let refreshable = self.cached(&url).is_some_and(|cached| {
+                    now.saturating_sub(cached.fetched_at) >= self.min_refresh_interval
+                });
+                if !refreshable {
+                    return Err(ImplementationError::NoSuchKey);
+                }
+                let keyring = self.refresh(&url, now)?;
+                verifier.verify(&keyring, key_id)
+            }
+            result => result,
+        }
+    }
+
+    fn cached(&self, url: &str) -> Option<CachedDirectory> {
+        let mut directories = self
+            .directories
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let cached = directories.get_mut(url)?;
+        cached.last_used = self.uses.fetch_add(1, Ordering::Relaxed);
+        Some(cached.clone())
+    }
+
+    fn refresh(&self, url: &str, now: Duration) -> Result<Arc<KeyRing>, ImplementationError> {
+        let keyring = Arc::new(self.fetcher.fetch(url)?);
+        let mut directories = self
+            .directories
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        while directories.len() >= self.max_entries && !directories.contains_key(url) {
+            let Some(least_recently_used) = directories
+                .iter()
+                .min_by_key(|(_, cached)| cached.last_used)
+                .map(|(url, _)| url.clone())
+            else {
+                break;
+            };
+            directories.remove(&least_recently_used);
+        }
+        directories.insert(
+            url.to_string(),
+            CachedDirectory {
+                fetched_at: now,
+                keyring: Arc::clone(&keyring),
+                last_used: self.uses.fetch_add(1, Ordering::Relaxed),
+            },
+        );
+        Ok(keyring)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_vector::{self, StandardTestVector};
+    use std::cell::Cell;
+
+    const DIRECTORY: &str =
+        "https://directory.example.com/.well-known/http-message-signatures-directory";
+
+    #[derive(Default)]
+    struct MockFetcher {
+        fetches: Cell<usize>,
+        keys: Vec<(Thumbprint, Vec<u8>)>,
+    }
+
+    impl KeyDirectoryFetcher for MockFetcher {
+        fn fetch(&self, url: &str) -> Result<KeyRing, ImplementationError> {
+            assert_eq!(url, DIRECTORY);
+            self.fetches.set(self.fetches.get() + 1);
+            Ok(KeyRing::from_iter(self.keys.clone()))
+        }
+    }
+
+    #[derive(Debug)]
+    struct ManualClock(Mutex<Duration>);
+
+    impl ManualClock {
+        fn advance(&self, by: Duration) {
+            *self.0.lock().unwrap() += by;
+        }
+    }
+
+    impl Clock for ManualClock {
+        fn now(&self) -> Result<Duration, ImplementationError> {
+            Ok(*self.0.lock().unwrap())
+        }
+    }
+
+    #[test]
+    fn test_directory_is_fetched_once_per_ttl() {
+        let clock = Arc::new(ManualClock(Mutex::new(Duration::from_secs(1_735_689_600))));
+        let cache = KeyDirectoryCache::new(
+            MockFetcher {
+                keys: vec![(test_vector::keyid(), test_vector::public_key())],
+                ..Default::default()
+            },
+            Duration::from_secs(300),
+        )
+        .with_clock(clock.clone());
+        let verifier = MessageVerifier::parse(&StandardTestVector, None, |_| true).unwrap();
+
+        cache.verify(DIRECTORY, &verifier, None).unwrap();
+        cache.verify(DIRECTORY, &verifier, None).unwrap();
+        assert_eq!(cache.fetcher.fetches.get(), 1);
+
+        clock.advance(Duration::from_secs(300));
+        cache.verify(DIRECTORY, &verifier, None).unwrap();
+        assert_eq!(cache.fetcher.fetches.get(), 2);
+
+        cache.invalidate(DIRECTORY);
+        cache.keyring(DIRECTORY).unwrap();
+        assert_eq!(cache.fetcher.fetches.get(), 3);
+    }
+
+    #[test]
+    fn test_missing_key_refreshes_at_most_once_per_interval() {
+        let clock = Arc::new(ManualClock(Mutex::new(Duration::from_secs(1_735_689_600))));
+        let cache = KeyDirectoryCache::new(MockFetcher::default(), Duration::from_secs(300))
+            .with_clock(clock.clone())
+            .with_min_refresh_interval(Duration::from_secs(60));
+        let verifier = MessageVerifier::parse(&StandardTestVector, None, |_| true).unwrap();
+
+        assert!(matches!(
+            cache.verify(DIRECTORY, &verifier, None),
+            Err(ImplementationError::NoSuchKey)
+        ));
+        assert!(matches!(
+            cache.verify(DIRECTORY, &verifier, None),
+            Err(ImplementationError::NoSuchKey)
+        ));
+        assert_eq!(cache.fetcher.fetches.get(), 1);
+
+        clock.advance(Duration::from_secs(60));
+        assert!(matches!(
+            cache.verify(DIRECTORY, &verifier, None),
+            Err(ImplementationError::NoSuchKey)
+        ));
+        assert_eq!(cache.fetcher.fetches.get(), 2);
+    }
+
+    #[derive(Default)]
+    struct RecordingFetcher(std::cell::RefCell<Vec<String>>);
+
+    impl KeyDirectoryFetcher for RecordingFetcher {
+        fn fetch(&self, url: &str) -> Result<KeyRing, ImplementationError> {
+            self.0.borrow_mut().push(url.to_string());
+            Ok(KeyRing::default())
+        }
+    }
+
+    #[test]
+    fn test_cache_is_keyed_by_normalized_url() {
+        let cache = KeyDirectoryCache::new(RecordingFetcher::default(), Duration::from_secs(300));
+
+        for url in [
+            DIRECTORY,
+            "HTTPS://Directory.Example.COM:443/.well-known/http-message-signatures-directory",
+            "https://user@directory.example.com/.well-known/http-message-signatures-directory#keys",
+        ] {
+            cache.keyring(url).unwrap();
+        }
+        cache.keyring("https://directory.example.com").unwrap();
+        cache.keyring("https://directory.example.com/").unwrap();
+        cache.keyring("data:application/json,{}").unwrap();
+        assert_eq!(
+            *cache.fetcher.0.borrow(),
+            [
+                DIRECTORY,
+                "https://directory.example.com/",
+                "data:application/json,{}"
+            ]
+        );
+        assert_eq!(cache.len(), 3);
+
+        cache.invalidate("https://DIRECTORY.example.com/");
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_cache_evicts_least_recently_used_directory() {
+        let cache = KeyDirectoryCache::new(RecordingFetcher::default(), Duration::from_secs(300))
+            .with_max_entries(2);
+
+        cache.keyring("https://a.example/").unwrap();
+        cache.keyring("https://b.example/").unwrap();
+        cache.keyring("https://a.example/").unwrap();
+        cache.keyring("https://c.example/").unwrap();
+        assert_eq!(cache.len(), 2);
+
+        cache.keyring("https://a.example/").unwrap();
+        cache.keyring("https://b.example/").unwrap();
+        assert_eq!(
+            *cache.fetcher.0.borrow(),
+            [
+                "https://a.example/",
+                "https://b.example/",
+                "https://c.example/",
+                "https://b.example/"
+            ]
+        );
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_keyring_from_directory() {
+        let document = format!(
+            r#"{{
+                "purpose": "web-bot-auth",
+                "keys": [
+                    {{
+                        "kty": "RSA",
+                        "n": "0vx7agoebGcQSuuPiLJXZptN9nndrQmbXEps2aiAFbWhM78LhWx4cbbfAAtVT86zwu1RK7aPFFxuhDR1L6tSoc_BJECPebWKRXjBZCiFV4n3oknjhMstn64tZ_2W",
+                        "e": "AQAB"
+                    }},
+                    {{
+                        "kty": "OKP",
+                        "crv": "Ed25519",
+                        "x": "{x}",
+                        "nbf": 1735689600,
+                        "meta": {{"note": "caf\u00e9 \ud83e\udd16", "rotated": [true, null, -1.5e3]}}
+                    }},
+                    {{"kty": "OKP", "crv": "X25519", "x": "{x}"}},
+                    {{"kty": "OKP", "crv": "Ed25519", "x": "AAAA"}},
+                    {{"kty": "OKP", "crv": "Ed25519", "x": "not base64!"}}
+                ]
+            }}"#,
+            x = URL_SAFE_NO_PAD.encode(test_vector::public_key())
+        );
+        let keyring = keyring_from_directory(&document).unwrap();
+        assert_eq!(
+            keyring.get(&test_vector::keyid()),
+            Some(&test_vector::public_key())
+        );
+        assert_eq!(keyring.len(), 1);
+
+        let verifier = MessageVerifier::parse(&StandardTestVector, None, |_| true).unwrap();
+        verifier.verify(&keyring, None).unwrap();
+
+        for invalid in [
+            "",
+            "[]",
+            r#"{"keys": {}}"#,
+            r#"{"keys": []"#,
+            r#"{"keys": []} []"#,
+            r#"{"keys": [], "note": "\ud83e"}"#,
+            &format!(r#"{{"keys": [], "deep": {}}}"#, "[".repeat(64)),
+        ] {
+            assert!(
+                matches!(
+                    keyring_from_directory(invalid),
+                    Err(ImplementationError::ParsingError(_))
+                ),
+                "{invalid}"
+            );
+        }
+
+        let too_many_keys = format!(
+            r#"{{"keys": [{}]}}"#,
+            vec!["{}"; MAX_DIRECTORY_KEYS + 1].join(",")
+        );
+        let too_long = format!(
+            r#"{{"keys": [], "note": "{}"}}"#,
+            "a".repeat(MAX_DIRECTORY_LENGTH)
+        );
+        for oversized in [too_many_keys, too_long] {
+            assert!(
+                keyring_from_directory(&oversized)
+                    .unwrap_err()
+                    .is_input_too_large()
+            );
+        }
+        let at_the_limit = format!(
+            r#"{{"keys": [{}]}}"#,
+            vec!["{}"; MAX_DIRECTORY_KEYS].join(",")
+        );
+        assert!(keyring_from_directory(&at_the_limit).unwrap().is_empty());
+    }
+}
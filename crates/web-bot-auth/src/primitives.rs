@@ -0,0 +1,218 @@
+// Copyright (c) 2025 Cloudflare, Inc.
+// Licensed under the Apache 2.0 license found in the LICENSE file or at:
+//     https://opensource.org/licenses/Apache-2.0
+
+use alloc::vec::Vec;
+use core::time::Duration;
+
+#[cfg(feature = "p384")]
+use super::p384_signing_key;
+#[cfg(feature = "rsa")]
+use super::rsa_signing_key;
+use super::{Algorithm, ImplementationError, SigningBackend, ed25519_signing_key};
+
+const LINE_SEPARATOR: &[u8] = b": ";
+const SIGNATURE_PARAMS_IDENTIFIER: &str = "\"@signature-params\"";
+
+// Byte offset of the first non-ASCII character in `s`, if any.
+fn first_non_ascii(s: &str) -> Option<usize> {
+    s.bytes().position(|byte| !byte.is_ascii())
+}
+
+/// Write a signature base into `output`. `components` are pairs of a serialized component
+/// identifier, such as `"@authority"` or `"content-type";sf`, and its value, in the order they
+/// are covered. `signature_params` is the serialized inner list terminating the base on its
+/// `@signature-params` line, such as `("@authority");created=1735689600`.
+///
+/// # Errors
+///
+/// Returns `ImplementationError::NonAsciiContentFound` if an identifier or value is not ASCII,
+/// naming the offending component. In that case, `output` is left partially written.
+pub fn write_signature_base<'a>(
+    output: &mut Vec<u8>,
+    components: impl IntoIterator<Item = (&'a str, &'a str)>,
+    signature_params: &str,
+) -> Result<(), ImplementationError> {
+    for (identifier, value) in components {
+        write_component_line(output, identifier, value)?;
+    }
+    write_signature_params_line(output, signature_params)
+}
+
+// Write the line of a signature base covering `identifier`, as `write_signature_base` does for
+// each of its `components`.
+pub(crate) fn write_component_line(
+    output: &mut Vec<u8>,
+    identifier: &str,
+    value: &str,
+) -> Result<(), ImplementationError> {
+    if let Some(offset) = first_non_ascii(identifier).or_else(|| {
+        first_non_ascii(value).map(|offset| identifier.len() + LINE_SEPARATOR.len() + offset)
+    }) {
+        return Err(ImplementationError::NonAsciiContentFound(
+            identifier.into(),
+            offset,
+        ));
+    }
+
+    output.extend_from_slice(identifier.as_bytes());
+    output.extend_from_slice(LINE_SEPARATOR);
+    output.extend_from_slice(value.as_bytes());
+    output.push(b'\n');
+    Ok(())
+}
+
+// Write the `@signature-params` line terminating a signature base.
+pub(crate) fn write_signature_params_line(
+    output: &mut Vec<u8>,
+    signature_params: &str,
+) -> Result<(), ImplementationError> {
+    if let Some(offset) = first_non_ascii(signature_params) {
+        return Err(ImplementationError::NonAsciiContentFound(
+            SIGNATURE_PARAMS_IDENTIFIER.into(),
+            SIGNATURE_PARAMS_IDENTIFIER.len() + LINE_SEPARATOR.len() + offset,
+        ));
+    }
+
+    output.extend_from_slice(SIGNATURE_PARAMS_IDENTIFIER.as_bytes());
+    output.extend_from_slice(LINE_SEPARATOR);
+    output.extend_from_slice(signature_params.as_bytes());
+
+    Ok(())
+}
+
+/// Length, in bytes, of the signature base `write_signature_base` would write for `components`
+/// and `signature_params`, computed without writing it.
+pub fn signature_base_length<'a>(
+    components: impl IntoIterator<Item = (&'a str, &'a str)>,
+    signature_params: &str,
+) -> usize {
+    components
+        .into_iter()
+        .fold(0usize, |length, (identifier, value)| {
+            length.saturating_add(identifier.len() + LINE_SEPARATOR.len() + value.len() + 1)
+        })
+        .saturating_add(
+            SIGNATURE_PARAMS_IDENTIFIER.len() + LINE_SEPARATOR.len() + signature_params.len(),
+        )
+}
+
+/// Sign the signature `base` with the raw `signing_key`, interpreted according to `algorithm`.
+/// For `Algorithm::Ed25519`, `signing_key` may be a 32-byte seed, a 64-byte keypair (the seed
+/// followed by the public key), or a PKCS#8 private key in either DER or PEM encoding. For
+/// `Algorithm::RsaV15Sha256`, it must be a DER-encoded PKCS#8 or PKCS#1 private key, and for
+/// `Algorithm::EcdsaP384Sha384` a 48-byte scalar or a DER-encoded PKCS#8 private key.
+///
+/// # Errors
+///
+/// Returns `ImplementationErrors` relevant to signing, and
+/// `ImplementationError::UnsupportedAlgorithm` if the feature `algorithm` requires is disabled.
+pub fn sign(
+    algorithm: &Algorithm,
+    base: &[u8],
+    signing_key: &[u8],
+) -> Result<Vec<u8>, ImplementationError> {
+    match algorithm {
+        Algorithm::Ed25519 => ed25519_signing_key(signing_key)?.sign(base),
+        #[cfg(feature = "rsa")]
+        Algorithm::RsaV15Sha256 => rsa_signing_key(signing_key)?.sign(base),
+        #[cfg(feature = "p384")]
+        Algorithm::EcdsaP384Sha384 => p384_signing_key(signing_key)?.sign(base),
+        #[allow(unreachable_patterns)]
+        _ => Err(ImplementationError::UnsupportedAlgorithm),
+    }
+}
+
+/// Verify `signature` over the signature `base` with the raw `public_key`, according to
+/// `algorithm`.
+///
+/// # Errors
+///
+/// Returns `ImplementationErrors` relevant to verifying.
+pub fn verify(
+    algorithm: &Algorithm,
+    base: &[u8],
+    signature: &[u8],
+    public_key: &[u8],
+) -> Result<(), ImplementationError> {
+    algorithm
+        .verify_signature(base, signature, public_key)
+        .map(|_| ())
+}
+
+/// Whether a signature whose `expires` parameter is `expires` has expired at `now`, expressed
+/// as a duration since the Unix epoch.
+pub fn is_expired(expires: i64, now: Duration) -> bool {
+    expires <= 0
+        || i64::try_from(now.as_secs())
+            .map(|now| now >= expires)
+            .unwrap_or(true)
+}
+
+/// Whether `a` and `b` are equal, in time independent of their contents, to compare digests and
+/// other secrets without leaking through timing how many leading bytes match. Only the lengths,
+/// which are usually public, may be inferred.
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    use subtle::ConstantTimeEq as _;
+
+    a.ct_eq(b).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signing_then_verifying_a_base() {
+        let mut base = Vec::new();
+        write_signature_base(
+            &mut base,
+            [("\"@authority\"", "example.com")],
+            "(\"@authority\");created=1735689600",
+        )
+        .unwrap();
+        assert_eq!(
+            base,
+            b"\"@authority\": example.com\n\"@signature-params\": (\"@authority\");created=1735689600"
+        );
+
+        let signing_key = [0x9f; ed25519_dalek::SECRET_KEY_LENGTH];
+        let public_key = ed25519_dalek::SigningKey::from_bytes(&signing_key)
+            .verifying_key()
+            .to_bytes();
+        let signature = sign(&Algorithm::Ed25519, &base, &signing_key).unwrap();
+        verify(&Algorithm::Ed25519, &base, &signature, &public_key).unwrap();
+
+        let mut non_ascii = Vec::new();
+        assert!(matches!(
+            write_signature_base(&mut non_ascii, [("\"x-name\"", "café")], "(\"x-name\")"),
+            Err(ImplementationError::NonAsciiContentFound(component, 13)) if component == "\"x-name\""
+        ));
+    }
+
+    #[test]
+    fn test_is_expired() {
+        assert!(!is_expired(
+            1_735_693_200,
+            Duration::from_secs(1_735_689_600)
+        ));
+        assert!(is_expired(
+            1_735_693_200,
+            Duration::from_secs(1_735_693_200)
+        ));
+        assert!(is_expired(0, Duration::ZERO));
+        assert!(is_expired(-1, Duration::from_secs(1_735_689_600)));
+        assert!(!is_expired(i64::MAX, Duration::from_secs(1_735_689_600)));
+        assert!(is_expired(i64::MAX, Duration::MAX));
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        assert!(ct_eq(b"", b""));
+        assert!(ct_eq(b"digest", b"digest"));
+        assert!(!ct_eq(b"digest", b"digesT"));
+        assert!(!ct_eq(b"digest", b"Digest"));
+        assert!(!ct_eq(b"digest", b"diges"));
+        assert!(!ct_eq(b"", b"d"));
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright (c) 2025 Cloudflare, Inc.
+// Licensed under the Apache 2.0 license found in the LICENSE file or at:
+//     https://opensource.org/licenses/Apache-2.0
+
+use super::{
+    PublicKey, SignedMessage, Thumbprint, WebBotAuthSignedMessage,
+    components::{CoveredComponent, DerivedComponent},
+};
+
+const PUBLIC_KEY: [u8; ed25519_dalek::PUBLIC_KEY_LENGTH] = [
+    0x26, 0xb4, 0x0b, 0x8f, 0x93, 0xff, 0xf3, 0xd8, 0x97, 0x11, 0x2f, 0x7e, 0xbc, 0x58, 0x2b, 0x23,
+    0x2d, 0xbd, 0x72, 0x51, 0x7d, 0x08, 0x2f, 0xe8, 0x3c, 0xfb, 0x30, 0xdd, 0xce, 0x43, 0xd1, 0xbb,
+];
+
+const PRIVATE_KEY: [u8; ed25519_dalek::SECRET_KEY_LENGTH] = [
+    0x9f, 0x83, 0x62, 0xf8, 0x7a, 0x48, 0x4a, 0x95, 0x4e, 0x6e, 0x74, 0x0c, 0x5b, 0x4c, 0x0e, 0x84,
+    0x22, 0x91, 0x39, 0xa2, 0x0a, 0xa8, 0xab, 0x56, 0xff, 0x66, 0x58, 0x6f, 0x6a, 0x7d, 0x29, 0xc5,
+];
+
+const SIGNATURE_PARAMS: &str = r#"("@authority");created=1735689600;keyid="poqkLGiymh_W0uP6PZFw-dvez3QJT5SolqXBCW38r0U";alg="ed25519";expires=1735693200;nonce="gubxywVx7hzbYKatLgzuKDllDAIXAkz41PydU7aOY7vT+Mb3GJNxW0qD4zJ+IOQ1NVtg+BNbTCRUMt1Ojr5BgA==";tag="web-bot-auth""#;
+
+/// The value of the `@authority` component covered by the standard test vector.
+pub fn authority() -> &'static str {
+    "example.com"
+}
+
+/// The value of the `Signature` header of the standard test vector, labelled `sig1`.
+pub fn signature() -> String {
+    "sig1=:uz2SAv+VIemw+Oo890bhYh6Xf5qZdLUgv6/PbiQfCFXcX/vt1A8Pf7OcgL2yUDUYXFtffNpkEr5W6dldqFrkDg==:"
+        .to_owned()
+}
+
+/// The value of the `Signature-Input` header of the standard test vector, labelled `sig1`.
+/// The signature was created at 1735689600 and expires at 1735693200, so is long expired.
+pub fn signature_input() -> String {
+    format!("sig1={SIGNATURE_PARAMS}")
+}
+
+/// The Ed25519 public key the standard test vector verifies against.
+pub fn public_key() -> PublicKey {
+    PUBLIC_KEY.to_vec()
+}
+
+/// The Ed25519 private key, as a 32-byte seed, the standard test vector was signed with.
+pub fn private_key() -> Vec<u8> {
+    PRIVATE_KEY.to_vec()
+}
+
+/// The JWK thumbprint of `public_key`, as found in the `keyid` parameter.
+pub fn keyid() -> Thumbprint {
+    "poqkLGiymh_W0uP6PZFw-dvez3QJT5SolqXBCW38r0U".to_owned()
+}
+
+/// The signature base a verifier must compute for the standard test vector.
+pub fn signature_base() -> String {
+    format!(
+        "\"@authority\": {}\n\"@signature-params\": {SIGNATURE_PARAMS}",
+        authority()
+    )
+}
+
+/// The standard test vector as a message, for comparison with a `SignedMessage`
+/// implementation under test.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StandardTestVector;
+
+impl SignedMessage for StandardTestVector {
+    fn fetch_signature_header(&self) -> Option<String> {
+        Some(signature())
+    }
+
+    fn fetch_signature_input(&self) -> Option<String> {
+        Some(signature_input())
+    }
+
+    fn lookup_component(&self, name: &CoveredComponent) -> Option<String> {
+        match name {
+            CoveredComponent::Derived(DerivedComponent::Authority { .. }) => {
+                Some(authority().to_owned())
+            }
+            _ => None,
+        }
+    }
+}
+
+impl WebBotAuthSignedMessage for StandardTestVector {
+    fn fetch_signature_agent(&self) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{KeyRing, MessageVerifier, SignatureBytes, verify_detached};
+
+    #[test]
+    fn test_fixture_verifies() {
+        let verifier = MessageVerifier::parse(&StandardTestVector, None, |_| true).unwrap();
+        assert_eq!(verifier.signature_base().unwrap(), signature_base());
+        verifier
+            .verify(&KeyRing::from_iter([(keyid(), public_key())]), None)
+            .unwrap();
+
+        let encoded = signature();
+        let signature =
+            SignatureBytes::from_base64(encoded.trim_start_matches("sig1=:").trim_end_matches(':'))
+                .unwrap();
+        verify_detached(
+            &signature_base(),
+            &signature,
+            &public_key(),
+            crate::Algorithm::Ed25519,
+        )
+        .unwrap();
+
+        assert_eq!(
+            ed25519_dalek::SigningKey::try_from(private_key().as_slice())
+                .unwrap()
+                .verifying_key()
+                .to_bytes()
+                .to_vec(),
+            public_key()
+        );
+    }
+}
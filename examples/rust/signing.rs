@@ -23,6 +23,8 @@ impl UnsignedMessage for MyThing {
         self.signature_input = format!("sig1={signature_input}");
         self.signature_header = format!("sig1={signature_header}");
     }
+
+    fn register_signature_agent(&mut self, _signature_agent: String) {}
 }
 
 fn main() {
@@ -32,12 +34,14 @@ fn main() {
         0x84, 0x22, 0x91, 0x39, 0xa2, 0x0a, 0xa8, 0xab, 0x56, 0xff, 0x66, 0x58, 0x6f, 0x6a, 0x7d,
         0x29, 0xc5,
     ];
-    let signer = MessageSigner {
-        algorithm: Algorithm::Ed25519,
-        keyid: "poqkLGiymh_W0uP6PZFw-dvez3QJT5SolqXBCW38r0U".into(),
-        nonce: "ZO3/XMEZjrvSnLtAP9M7jK0WGQf3J+pbmQRUpKDhF9/jsNCWqUh2sq+TH4WTX3/GpNoSZUa8eNWMKqxWp2/c2g==".into(),
-        tag: "web-bot-auth".into(),
-    };
+    let signer = MessageSigner::new(
+        Algorithm::Ed25519,
+        "poqkLGiymh_W0uP6PZFw-dvez3QJT5SolqXBCW38r0U",
+    )
+    .with_nonce(
+        "ZO3/XMEZjrvSnLtAP9M7jK0WGQf3J+pbmQRUpKDhF9/jsNCWqUh2sq+TH4WTX3/GpNoSZUa8eNWMKqxWp2/c2g==",
+    )
+    .with_tag("web-bot-auth");
     let mut headers = MyThing::default();
     signer
         .generate_signature_headers_content(&mut headers, Duration::from_secs(10), &private_key)
@@ -0,0 +1,69 @@
+use std::time::Duration;
+use web_bot_auth::{
+    Algorithm, MessageSigner,
+    components::{ComponentsBuilder, request_target_value},
+    content_digest::{DigestAlgorithm, content_digest_value},
+};
+
+fn main() {
+    // Signing a complete HTTP request, printing everything to send for it
+    let method = "POST";
+    let url = "https://example.com/api/items?page=2";
+    let body = br#"{"hello": "world"}"#;
+    let signature_agent = "https://signature-agent.example.com";
+
+    let (_scheme, rest) = url.split_once("://").unwrap();
+    let (authority, path_and_query) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    let (path, query) = match path_and_query.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (path_and_query, None),
+    };
+
+    // `Content-Digest` binds the body to the signature, see RFC 9530
+    let content_digest = content_digest_value(body, &[DigestAlgorithm::Sha256]);
+    let components_to_cover = ComponentsBuilder::new()
+        .method(method)
+        .authority(authority.to_ascii_lowercase())
+        .path(path)
+        .header("content-digest", content_digest.clone())
+        .build();
+
+    let private_key = vec![
+        0x9f, 0x83, 0x62, 0xf8, 0x7a, 0x48, 0x4a, 0x95, 0x4e, 0x6e, 0x74, 0x0c, 0x5b, 0x4c, 0x0e,
+        0x84, 0x22, 0x91, 0x39, 0xa2, 0x0a, 0xa8, 0xab, 0x56, 0xff, 0x66, 0x58, 0x6f, 0x6a, 0x7d,
+        0x29, 0xc5,
+    ];
+    let signer = MessageSigner::new(
+        Algorithm::Ed25519,
+        "poqkLGiymh_W0uP6PZFw-dvez3QJT5SolqXBCW38r0U",
+    )
+    .with_nonce(
+        "ZO3/XMEZjrvSnLtAP9M7jK0WGQf3J+pbmQRUpKDhF9/jsNCWqUh2sq+TH4WTX3/GpNoSZUa8eNWMKqxWp2/c2g==",
+    )
+    .with_tag("web-bot-auth")
+    // Points verifiers at the key directory, and is covered as `signature-agent`
+    .with_signature_agent(signature_agent);
+    let (signature_input, signature) = signer
+        .sign_with_label(
+            "sig1",
+            components_to_cover,
+            Duration::from_secs(60),
+            &private_key,
+        )
+        .unwrap();
+    let signature_agent = signer.signature_agent_content().unwrap().unwrap();
+
+    println!(
+        "{method} {} HTTP/1.1",
+        request_target_value(method, authority, path, query)
+    );
+    println!("Host: {authority}");
+    println!("Content-Type: application/json");
+    println!("Content-Length: {}", body.len());
+    println!("Content-Digest: {content_digest}");
+    println!("Signature-Agent: {signature_agent}");
+    println!("Signature-Input: {signature_input}");
+    println!("Signature: {signature}");
+    println!();
+    println!("{}", String::from_utf8_lossy(body));
+}